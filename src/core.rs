@@ -1,19 +1,22 @@
 use std::{
 	fmt::{Display, Formatter},
 };
-use itertools::Itertools;
+use crate::field::Field;
 
-/// Matrix struct
+/// Matrix struct, generic over the [`Field`] its entries live in
+///
+/// Defaults to `f64` so existing real-valued usage (`Matrix::new(...)`
+/// without a turbofish) keeps working unchanged.
 #[derive(Clone)]
-pub struct Matrix {
-	contents: Vec<Vec<f64>>,
-	l: usize,
-	w: usize
+pub struct Matrix<F: Field = f64> {
+	pub(crate) contents: Vec<Vec<F>>,
+	pub(crate) l: usize,
+	pub(crate) w: usize
 }
 
-impl Matrix {
+impl<F: Field> Matrix<F> {
 	/// Makes a new matrix from
-	pub fn new(mut inner: Vec<Vec<f64>>) -> Option<Self> {
+	pub fn new(inner: Vec<Vec<F>>) -> Option<Self> {
 		let l = inner.len();
 		let w;
 		let mut contents = vec![];
@@ -32,126 +35,98 @@ impl Matrix {
 			contents, l, w
 		})
 	}
-	
+
 	pub fn identity(n: usize) -> Self {
 		Matrix {
 			contents: (0..n).fold(vec![], |mut acc, arg| {
-				let mut temp = vec![0.; n];
-				temp[arg] = 1.;
+				let mut temp = vec![F::zero(); n];
+				temp[arg] = F::one();
 				acc.push(temp);
 				acc
 			}),
 			l: n, w: n
 		}
 	}
-	
+
 	pub fn blank(l: usize, w: usize) -> Self {
 		Matrix {
-			contents: vec![vec![0.; w]; l],
+			contents: vec![vec![F::zero(); w]; l],
 			l, w
 		}
 	}
-	
-	pub fn lu_decompose(&self) -> Option<(Self, Self)> {
-		if self.l != self.w {
-			return None
-		}
-		let mut upper = self.clone();
-		let mut lower = Matrix::identity(self.l);
-		for r in 0..self.l {
-			// Check if the element in position (r, r) is 0
-			let scale = upper.contents[r][r];
-			if scale == 0. {
-				return None
+
+	/// Applies a single elementary row operation in place
+	///
+	/// # Panics
+	/// Panics if a row index is out of bounds, or if [`ERO::Scale`] is given
+	/// a zero factor (scaling by zero isn't invertible, so isn't a valid ERO).
+	pub fn apply(&mut self, op: ERO<F>) {
+		match op {
+			ERO::Scale(row, factor) => {
+				assert!(row < self.l, "row {row} out of bounds");
+				assert!(factor != F::zero(), "cannot scale a row by zero");
+				for v in self.contents[row].iter_mut() {
+					*v = v.mul(factor);
+				}
 			}
-			// Scale the r-th row in the upper to give it a leading 1
-			upper.contents[r] = upper.contents.get(r).unwrap().iter().map(|t| t / scale).collect();
-			// Scale the r-th row in the lower (inverse of the previous ERO)
-			lower.contents[r][r] = scale;
-			// Add a scaled version of the r-th row to all the rows below it
-			let short_row_upper = upper.contents[r][r + 1..].to_vec().clone();
-			for below in r + 1..self.l {
-				let scale = upper.contents[below][r];
-				if scale == 0. {
-					continue
+			ERO::Add(dst, src, factor) => {
+				assert!(dst < self.l && src < self.l, "row out of bounds");
+				let scaled: Vec<F> = self.contents[src].iter().map(|v| v.mul(factor)).collect();
+				for (v, s) in self.contents[dst].iter_mut().zip(scaled) {
+					*v = v.add(s);
 				}
-				let mut new_row = vec![0.; r + 1];
-				new_row.extend(
-					upper.contents[below][r + 1..].to_vec().iter().zip(short_row_upper.iter()).map(
-						|(lower_item, r_row_item)| lower_item - scale * r_row_item
-					)
-				);
-				upper.contents[below] = new_row;
-				lower.contents[below][r] = scale
+			}
+			ERO::Switch(a, b) => {
+				assert!(a < self.l && b < self.l, "row out of bounds");
+				self.contents.swap(a, b);
 			}
 		}
-		Some((lower, upper))
 	}
-	
-	pub fn plu_decomposition(&self) -> Option<(Self, Self, Self)> {
-		if self.l != self.w {
-			return None
-		}
-		let mut out = self.clone();
-		if let Some((lower, upper)) = out.lu_decompose() {
-			return Some((lower, upper, Matrix::identity(self.l)))
-		}
-		for mut t in (0..self.w).combinations(2) {
-			let mut permutation = Matrix::identity(self.l);
-			let row = permutation.contents.get(*t.first().unwrap()).unwrap().clone();
-			let row2 = permutation.contents.get(*t.last().unwrap()).unwrap().clone();
-			permutation.contents[*t.last().unwrap()] = row;
-			permutation.contents[*t.first().unwrap()] = row2;
-			
-			let row = out.contents.get(*t.first().unwrap()).unwrap().clone();
-			let row2 = out.contents.get(*t.last().unwrap()).unwrap().clone();
-			out.contents[*t.last().unwrap()] = row;
-			out.contents[*t.first().unwrap()] = row2;
-			if let Some((lower, upper)) = out.lu_decompose() {
-				return Some((lower, upper, permutation))
-			}
+
+	/// Applies a sequence of elementary row operations in place, in order
+	pub fn apply_all(&mut self, ops: &[ERO<F>]) {
+		for op in ops {
+			self.apply(*op);
 		}
-		None
 	}
 }
 
-impl Display for Matrix {
+impl<F: Field + Display> Display for Matrix<F> {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-		let t = f.precision().unwrap_or(10);
 		write!(f, "{}", self.contents.iter().map(
 			|r| format!("|{}|",
-						r.iter().map(|i| format!("{}{:.t$}", if i < &0. { '-' } else { ' ' }, i.abs())).collect::<Vec<String>>().join(" ,")
+						r.iter().map(|i| format!("{i}")).collect::<Vec<String>>().join(" ,")
 			)
 		).collect::<Vec<String>>().join("\n"))
 	}
 }
 
-impl std::ops::Add<Matrix> for Matrix {
-	type Output = Matrix;
-	
-	fn add(self, rhs: Matrix) -> Self::Output {
+impl<F: Field> std::ops::Add<Matrix<F>> for Matrix<F> {
+	type Output = Matrix<F>;
+
+	fn add(self, rhs: Matrix<F>) -> Self::Output {
 		assert_eq!(self.l, rhs.l);
 		assert_eq!(self.w, rhs.w);
 		let mut out = self.clone();
 		for i in 0..self.l {
 			for j in 0..self.w {
-				out.contents[i][j] += rhs.contents[i][j]
+				out.contents[i][j] = out.contents[i][j].add(rhs.contents[i][j]);
 			}
 		}
 		out
 	}
 }
 
-impl std::ops::Mul<Matrix> for Matrix {
-	type Output = Matrix;
-	
-	fn mul(self, rhs: Matrix) -> Self::Output {
+impl<F: Field> std::ops::Mul<Matrix<F>> for Matrix<F> {
+	type Output = Matrix<F>;
+
+	fn mul(self, rhs: Matrix<F>) -> Self::Output {
 		assert_eq!(self.w, rhs.l);
 		let mut out = Matrix::blank(self.l, rhs.w);
 		for i in 0..self.l {
 			for j in 0..rhs.w {
 				out.contents[i][j] = (0..self.w).fold(
-					0., |acc, t| acc + self.contents[i][t] * rhs.contents[t][j]
+					F::zero(), |acc, t| acc.add(self.contents[i][t].mul(rhs.contents[t][j]))
 				)
 			}
 		}
@@ -159,39 +134,37 @@ impl std::ops::Mul<Matrix> for Matrix {
 	}
 }
 
-impl<T> std::ops::Mul<T> for Matrix where
-	T: Into<f64>
-{
-	type Output = Matrix;
-	
-	fn mul(self, rhs: T) -> Self::Output {
-		let rhs: f64 = rhs.into();
+impl<F: Field> std::ops::Mul<F> for Matrix<F> {
+	type Output = Matrix<F>;
+
+	fn mul(self, rhs: F) -> Self::Output {
 		let mut out = self.clone();
 		for i in 0..self.l {
 			for j in 0..self.w {
-				out.contents[i][j] *= rhs;
+				out.contents[i][j] = out.contents[i][j].mul(rhs);
 			}
 		}
 		out
 	}
 }
 
-impl<T> std::ops::Div<T> for Matrix where
-	T: Into<f64>
-{
-	type Output = Matrix;
-	
-	fn div(self, rhs: T) -> Self::Output {
-		let rhs: f64 = rhs.into();
-		self * (1. / rhs)
+impl<F: Field> std::ops::Div<F> for Matrix<F> {
+	type Output = Matrix<F>;
+
+	// Division is multiplication by the inverse, so delegating to `Mul` here
+	// is intentional, not a mixed-operator slip.
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn div(self, rhs: F) -> Self::Output {
+		self * rhs.inverse().expect("cannot divide a matrix by a zero scalar")
 	}
 }
 
-pub enum ERO {
+#[derive(Copy, Clone)]
+pub enum ERO<F: Field = f64> {
 	/// Scale the first row by the second value
-	Scale(usize, f64),
+	Scale(usize, F),
 	/// Add the scaled second row (by the third value) to the first row
-	Add(usize, usize, f64),
+	Add(usize, usize, F),
 	/// Switch the two rows
 	Switch(usize, usize)
 }
@@ -1,9 +1,9 @@
 //! Decomposition implementations for the `matrix` struct
 
-use itertools::Itertools;
-use crate::core::Matrix;
+use crate::core::{Matrix, ERO};
+use crate::field::{Field, PivotField};
 
-impl Matrix {
+impl<F: Field> Matrix<F> {
 	/// Calculates the LU decomposition of a matrix
 	///
 	/// Returns `Option::None` is the LU decomposition cannot be found
@@ -16,24 +16,25 @@ impl Matrix {
 		for r in 0..self.l {
 			// Check if the element in position (r, r) is 0
 			let scale = upper.contents[r][r];
-			if scale == 0. {
+			if scale == F::zero() {
 				return None
 			}
+			let inv_scale = scale.inverse()?;
 			// Scale the r-th row in the upper to give it a leading 1
-			upper.contents[r] = upper.contents.get(r).unwrap().iter().map(|t| t / scale).collect();
+			upper.contents[r] = upper.contents.get(r).unwrap().iter().map(|t| t.mul(inv_scale)).collect();
 			// Scale the r-th row in the lower (inverse of the previous ERO)
 			lower.contents[r][r] = scale;
 			// Add a scaled version of the r-th row to all the rows below it
 			let short_row_upper = upper.contents[r][r + 1..].to_vec().clone();
 			for below in r + 1..self.l {
 				let scale = upper.contents[below][r];
-				if scale == 0. {
+				if scale == F::zero() {
 					continue
 				}
-				let mut new_row = vec![0.; r + 1];
+				let mut new_row = vec![F::zero(); r + 1];
 				new_row.extend(
 					upper.contents[below][r + 1..].to_vec().iter().zip(short_row_upper.iter()).map(
-						|(lower_item, r_row_item)| lower_item - scale * r_row_item
+						|(lower_item, r_row_item)| lower_item.sub(scale.mul(*r_row_item))
 					)
 				);
 				upper.contents[below] = new_row;
@@ -42,30 +43,207 @@ impl Matrix {
 		}
 		Some((lower, upper))
 	}
-	
+
+	/// Reduces the matrix to reduced row echelon form via Gauss-Jordan
+	/// elimination, recording every elementary row operation applied
+	///
+	/// Replaying the returned ops against a clone of `self` with
+	/// [`Matrix::apply_all`] reproduces this reduction exactly.
+	pub fn row_reduce_logged(&self) -> (Matrix<F>, Vec<ERO<F>>) {
+		let mut out = self.clone();
+		let mut ops = vec![];
+		let mut pivot_row = 0;
+		for col in 0..out.w {
+			if pivot_row >= out.l {
+				break
+			}
+			let Some(found) = (pivot_row..out.l).find(|&r| out.contents[r][col] != F::zero()) else {
+				continue
+			};
+			if found != pivot_row {
+				let op = ERO::Switch(pivot_row, found);
+				out.apply(op);
+				ops.push(op);
+			}
+			let pivot = out.contents[pivot_row][col];
+			if pivot != F::one() {
+				let op = ERO::Scale(pivot_row, pivot.inverse().unwrap());
+				out.apply(op);
+				ops.push(op);
+			}
+			for r in 0..out.l {
+				if r == pivot_row {
+					continue
+				}
+				let factor = out.contents[r][col];
+				if factor == F::zero() {
+					continue
+				}
+				let op = ERO::Add(r, pivot_row, F::zero().sub(factor));
+				out.apply(op);
+				ops.push(op);
+			}
+			pivot_row += 1;
+		}
+		(out, ops)
+	}
+}
+
+/// Sign of a permutation matrix: `-1` to the power of the number of
+/// transpositions needed to reach it from the identity. A cycle of length
+/// `c` takes `c - 1` transpositions, so each even-length cycle flips the sign
+fn permutation_sign<F: Field>(permutation: &Matrix<F>) -> f64 {
+	let n = permutation.l;
+	let perm: Vec<usize> = (0..n).map(|r|
+		(0..n).find(|&c| permutation.contents[r][c] == F::one()).unwrap()
+	).collect();
+	let mut visited = vec![false; n];
+	let mut sign = 1.;
+	for start in 0..n {
+		if visited[start] {
+			continue
+		}
+		let mut cycle_len = 0;
+		let mut j = start;
+		while !visited[j] {
+			visited[j] = true;
+			j = perm[j];
+			cycle_len += 1;
+		}
+		if cycle_len % 2 == 0 {
+			sign *= -1.;
+		}
+	}
+	sign
+}
+
+impl<F: PivotField> Matrix<F> {
+	/// Calculates the `PLU` decomposition with partial pivoting, rejecting a
+	/// pivot (and returning `None`) only when its [`PivotField::pivot_score`]
+	/// is at or below `tolerance`
+	///
+	/// Single `O(n^3)` pass: at each column `k` the entry of best pivot score
+	/// among rows `k..n` is swapped into position `k` (recorded in `P`), and
+	/// its multipliers are accumulated directly into `L` (which ends up with
+	/// the pivots on its diagonal; `U` is unit-upper-triangular), exactly as
+	/// in [`Matrix::lu_decompose`].
+	pub fn plu_decomposition_with_tolerance(&self, tolerance: f64) -> Option<(Self, Self, Self)> {
+		if self.l != self.w {
+			return None
+		}
+		let n = self.l;
+		let mut upper = self.clone();
+		// Starts blank, not the identity: every diagonal entry is explicitly
+		// written below, and pre-seeding it would leave stray 1s behind when
+		// a not-yet-processed row is swapped out from under its own diagonal.
+		let mut lower = Matrix::blank(n, n);
+		let mut permutation = Matrix::identity(n);
+		for k in 0..n {
+			let pivot_row = (k..n).max_by(|&a, &b|
+				upper.contents[a][k].pivot_score().partial_cmp(&upper.contents[b][k].pivot_score()).unwrap()
+			)?;
+			if upper.contents[pivot_row][k].pivot_score() <= tolerance {
+				return None
+			}
+			if pivot_row != k {
+				upper.contents.swap(k, pivot_row);
+				lower.contents.swap(k, pivot_row);
+				permutation.contents.swap(k, pivot_row);
+			}
+			let scale = upper.contents[k][k];
+			let inv_scale = scale.inverse()?;
+			upper.contents[k] = upper.contents[k].iter().map(|t| t.mul(inv_scale)).collect();
+			lower.contents[k][k] = scale;
+			let short_row_upper = upper.contents[k][k + 1..].to_vec();
+			for below in k + 1..n {
+				let scale = upper.contents[below][k];
+				if scale == F::zero() {
+					continue
+				}
+				let mut new_row = vec![F::zero(); k + 1];
+				new_row.extend(
+					upper.contents[below][k + 1..].iter().zip(short_row_upper.iter()).map(
+						|(lower_item, r_row_item)| lower_item.sub(scale.mul(*r_row_item))
+					)
+				);
+				upper.contents[below] = new_row;
+				lower.contents[below][k] = scale;
+			}
+		}
+		Some((lower, upper, permutation))
+	}
+
+	/// `PLU` decomposition with a sensible default tolerance: a small
+	/// multiple of machine epsilon scaled by the matrix's largest entry
+	/// (by pivot score), so only pivots indistinguishable from zero at
+	/// `f64` precision are rejected as singular.
 	pub fn plu_decomposition(&self) -> Option<(Self, Self, Self)> {
+		let norm = self.contents.iter().flatten().map(|v| v.pivot_score()).fold(0., f64::max);
+		let tolerance = f64::EPSILON * (self.l.max(1) as f64) * norm;
+		self.plu_decomposition_with_tolerance(tolerance)
+	}
+}
+
+impl Matrix<f64> {
+	/// Determinant of a square matrix, computed from its PLU decomposition
+	///
+	/// `U` is unit-upper-triangular after [`Matrix::lu_decompose`], so the
+	/// determinant is just the product of `L`'s diagonal (the pivots),
+	/// flipped in sign for each row swap `plu_decomposition` performed.
+	///
+	/// Returns `None` for non-square matrices, or singular ones (no PLU
+	/// decomposition exists).
+	pub fn det(&self) -> Option<f64> {
 		if self.l != self.w {
 			return None
 		}
-		let mut out = self.clone();
-		if let Some((lower, upper)) = out.lu_decompose() {
-			return Some((lower, upper, Matrix::identity(self.l)))
+		let (lower, _, permutation) = self.plu_decomposition()?;
+		let pivot_product: f64 = (0..self.l).map(|i| lower.contents[i][i]).product();
+		Some(permutation_sign(&permutation) * pivot_product)
+	}
+
+	/// Inverts a square matrix by solving `A X = I`
+	///
+	/// Returns `None` for non-square or singular matrices.
+	pub fn inverse(&self) -> Option<Matrix> {
+		if self.l != self.w {
+			return None
 		}
-		for mut t in (0..self.w).combinations(2) {
-			let mut permutation = Matrix::identity(self.l);
-			let row = permutation.contents.get(*t.first().unwrap()).unwrap().clone();
-			let row2 = permutation.contents.get(*t.last().unwrap()).unwrap().clone();
-			permutation.contents[*t.last().unwrap()] = row;
-			permutation.contents[*t.first().unwrap()] = row2;
-			
-			let row = out.contents.get(*t.first().unwrap()).unwrap().clone();
-			let row2 = out.contents.get(*t.last().unwrap()).unwrap().clone();
-			out.contents[*t.last().unwrap()] = row;
-			out.contents[*t.first().unwrap()] = row2;
-			if let Some((lower, upper)) = out.lu_decompose() {
-				return Some((lower, upper, permutation))
+		self.solve(&Matrix::identity(self.l))
+	}
+
+	/// Solves `A x = b` for one or more right-hand sides via the `PLU`
+	/// factors: permute `b` by `P`, forward-substitute through `L`,
+	/// back-substitute through `U`
+	///
+	/// `b` may have several columns, solving several systems that share `A`
+	/// at once. Returns `None` if `A` is singular, or if `A` and `b` don't
+	/// have the same number of rows.
+	pub fn solve(&self, b: &Matrix) -> Option<Matrix> {
+		if self.l != self.w || self.l != b.l {
+			return None
+		}
+		let n = self.l;
+		let (lower, upper, permutation) = self.plu_decomposition()?;
+		let pb = permutation * b.clone();
+		let mut columns = vec![vec![0.; b.w]; n];
+		for col in 0..b.w {
+			// Forward substitution: L has the pivots on its diagonal here
+			let mut y = vec![0.; n];
+			for i in 0..n {
+				let sum: f64 = (0..i).map(|j| lower.contents[i][j] * y[j]).sum();
+				y[i] = (pb.contents[i][col] - sum) / lower.contents[i][i];
+			}
+			// Back substitution: U is unit-upper-triangular
+			let mut x = vec![0.; n];
+			for i in (0..n).rev() {
+				let sum: f64 = (i + 1..n).map(|j| upper.contents[i][j] * x[j]).sum();
+				x[i] = y[i] - sum;
+			}
+			for (row, value) in columns.iter_mut().zip(x) {
+				row[col] = value;
 			}
 		}
-		None
+		Matrix::new(columns)
 	}
 }
@@ -0,0 +1,140 @@
+//! Scalar fields that a [`Matrix`](crate::core::Matrix) can be generic over
+//!
+//! This crate started out hardcoded to `f64`, but the decomposition machinery
+//! (`lu_decompose`, `row_reduce_logged`, ...) only ever needs a handful of
+//! field operations to work; `plu_decomposition` additionally needs a pivot
+//! score from [`PivotField`]. [`Field`] captures exactly those, [`f64`]
+//! implements it for the default real-valued matrices, and [`GF256`] gives us
+//! arithmetic over `GF(2^8)` for finite-field uses such as erasure coding.
+
+use std::fmt::{Display, Formatter};
+use std::sync::OnceLock;
+
+/// A field a [`Matrix`](crate::core::Matrix) can be generic over
+///
+/// Only the operations the decomposition/solve machinery actually needs are
+/// required: additive and multiplicative identities, addition, subtraction,
+/// multiplication, and multiplicative inverse (which is `None` only for `0`).
+pub trait Field: Copy + Clone + PartialEq {
+	/// The additive identity
+	fn zero() -> Self;
+	/// The multiplicative identity
+	fn one() -> Self;
+	/// `self + rhs`
+	fn add(self, rhs: Self) -> Self;
+	/// `self - rhs`
+	fn sub(self, rhs: Self) -> Self;
+	/// `self * rhs`
+	fn mul(self, rhs: Self) -> Self;
+	/// `1 / self`, or `None` if `self` is `0`
+	fn inverse(self) -> Option<Self>;
+}
+
+impl Field for f64 {
+	fn zero() -> Self { 0. }
+	fn one() -> Self { 1. }
+	fn add(self, rhs: Self) -> Self { self + rhs }
+	fn sub(self, rhs: Self) -> Self { self - rhs }
+	fn mul(self, rhs: Self) -> Self { self * rhs }
+	fn inverse(self) -> Option<Self> {
+		if self == 0. {
+			None
+		} else {
+			Some(1. / self)
+		}
+	}
+}
+
+/// The primitive polynomial `x^8 + x^4 + x^3 + x^2 + 1` used to build the
+/// `GF(2^8)` log/exp tables
+const PRIMITIVE_POLY: u16 = 0x11D;
+
+/// Lazily built `(exp, log)` tables for `GF(2^8)` multiplication
+///
+/// `exp[i] = g^i` for the generator `g = 2`, with the table extended to
+/// `512` entries so `exp[log[a] + log[b]]` never needs to wrap. `log[a]` is
+/// the discrete log of `a` base `g` (`log[0]` is unused/left `0`).
+fn tables() -> &'static ([u16; 512], [u8; 256]) {
+	static TABLES: OnceLock<([u16; 512], [u8; 256])> = OnceLock::new();
+	TABLES.get_or_init(|| {
+		let mut exp = [0u16; 512];
+		let mut log = [0u8; 256];
+		let mut x: u16 = 1;
+		for (i, slot) in exp.iter_mut().enumerate().take(255) {
+			*slot = x;
+			log[x as usize] = i as u8;
+			x <<= 1;
+			if x & 0x100 != 0 {
+				x ^= PRIMITIVE_POLY;
+			}
+		}
+		for i in 255..512 {
+			exp[i] = exp[i - 255];
+		}
+		(exp, log)
+	})
+}
+
+/// An element of the finite field `GF(2^8)`
+///
+/// Addition (and subtraction, which is the same operation) is byte-wise XOR;
+/// multiplication is done via precomputed log/exp tables built from the
+/// primitive polynomial `0x11D`.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct GF256(pub u8);
+
+impl GF256 {
+	/// Raw byte value of this field element
+	pub fn byte(self) -> u8 { self.0 }
+}
+
+impl Field for GF256 {
+	fn zero() -> Self { GF256(0) }
+	fn one() -> Self { GF256(1) }
+	fn add(self, rhs: Self) -> Self { GF256(self.0 ^ rhs.0) }
+	fn sub(self, rhs: Self) -> Self { GF256(self.0 ^ rhs.0) }
+	fn mul(self, rhs: Self) -> Self {
+		if self.0 == 0 || rhs.0 == 0 {
+			return GF256(0)
+		}
+		let (exp, log) = tables();
+		GF256(exp[log[self.0 as usize] as usize + log[rhs.0 as usize] as usize] as u8)
+	}
+	fn inverse(self) -> Option<Self> {
+		if self.0 == 0 {
+			return None
+		}
+		let (exp, log) = tables();
+		Some(GF256(exp[255 - log[self.0 as usize] as usize] as u8))
+	}
+}
+
+impl From<u8> for GF256 {
+	fn from(value: u8) -> Self { GF256(value) }
+}
+
+/// A [`Field`] with a notion of how good a pivot candidate an element is,
+/// used for partial pivoting during decomposition
+///
+/// For floating-point fields this is the magnitude, so the numerically
+/// largest entry is preferred; for exact fields like [`GF256`] any nonzero
+/// entry is an equally good pivot. Implementations must return `0.0` if and
+/// only if the element is the field's zero.
+pub trait PivotField: Field {
+	/// How good a pivot candidate `self` is; higher is preferred
+	fn pivot_score(self) -> f64;
+}
+
+impl PivotField for f64 {
+	fn pivot_score(self) -> f64 { self.abs() }
+}
+
+impl PivotField for GF256 {
+	fn pivot_score(self) -> f64 { if self.0 == 0 { 0. } else { 1. } }
+}
+
+impl Display for GF256 {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:02x}", self.0)
+	}
+}
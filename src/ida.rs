@@ -0,0 +1,147 @@
+//! Rabin information dispersal (erasure coding) built on `Matrix<GF256>`
+//!
+//! [`split`] encodes `data` into `n` shares such that any `k` of them are
+//! enough for [`reconstruct`] to recover the original bytes. Share `i` holds,
+//! for each `k`-byte chunk of the (length-prefixed, padded) input, the dot
+//! product of row `i` of an `n×k` Vandermonde matrix over `GF(2^8)` with
+//! that chunk.
+
+use crate::core::Matrix;
+use crate::field::{Field, GF256};
+
+/// Builds the `n×k` Vandermonde generator matrix: row `i` is
+/// `[x_i^0, x_i^1, ..., x_i^(k-1)]` for a distinct nonzero `x_i` per share
+fn generator(n: usize, k: usize) -> Matrix<GF256> {
+	let rows = (1..=n as u16).map(|i| {
+		let x = GF256(i as u8);
+		let mut row = Vec::with_capacity(k);
+		let mut pow = GF256::one();
+		for _ in 0..k {
+			row.push(pow);
+			pow = pow.mul(x);
+		}
+		row
+	}).collect();
+	Matrix::new(rows).expect("generator rows all have length k")
+}
+
+/// The `k×k` Vandermonde submatrix for the given share ids, used to invert
+/// back to the original columns during reconstruction
+fn share_matrix(ids: &[u8]) -> Option<Matrix<GF256>> {
+	let k = ids.len();
+	let rows = ids.iter().map(|&id| {
+		let x = GF256(id);
+		let mut row = Vec::with_capacity(k);
+		let mut pow = GF256::one();
+		for _ in 0..k {
+			row.push(pow);
+			pow = pow.mul(x);
+		}
+		row
+	}).collect();
+	Matrix::new(rows)
+}
+
+/// Inverts a square `Matrix<GF256>` via Gauss-Jordan elimination
+///
+/// Returns `None` if the matrix is singular (no nonzero pivot in some column)
+fn invert(m: &Matrix<GF256>) -> Option<Matrix<GF256>> {
+	if m.l != m.w {
+		return None
+	}
+	let n = m.l;
+	let mut left = m.clone();
+	let mut right: Matrix<GF256> = Matrix::identity(n);
+	for col in 0..n {
+		let pivot_row = (col..n).find(|&r| left.contents[r][col] != GF256::zero())?;
+		if pivot_row != col {
+			left.contents.swap(col, pivot_row);
+			right.contents.swap(col, pivot_row);
+		}
+		let inv_pivot = left.contents[col][col].inverse()?;
+		for v in left.contents[col].iter_mut() { *v = v.mul(inv_pivot); }
+		for v in right.contents[col].iter_mut() { *v = v.mul(inv_pivot); }
+		for r in 0..n {
+			if r == col {
+				continue
+			}
+			let factor = left.contents[r][col];
+			if factor == GF256::zero() {
+				continue
+			}
+			for c in 0..n {
+				left.contents[r][c] = left.contents[r][c].sub(factor.mul(left.contents[col][c]));
+				right.contents[r][c] = right.contents[r][c].sub(factor.mul(right.contents[col][c]));
+			}
+		}
+	}
+	Some(right)
+}
+
+/// Splits `data` into `n` shares, any `k` of which are enough to
+/// [`reconstruct`] the original bytes
+///
+/// Each share is tagged with the nonzero `GF(2^8)` element (`1..=n`, as a
+/// raw byte) that identifies its row in the generator matrix. `k` must be at
+/// least `1` and at most `n`, and `n` must fit in a byte.
+pub fn split(data: &[u8], n: usize, k: usize) -> Vec<(u8, Vec<u8>)> {
+	assert!(k > 0 && k <= n && n <= 255);
+	let gen = generator(n, k);
+
+	// Prefix the original length so reconstruct can strip the padding again
+	let mut payload = (data.len() as u64).to_le_bytes().to_vec();
+	payload.extend_from_slice(data);
+	let pad = (k - payload.len() % k) % k;
+	payload.extend(std::iter::repeat_n(0u8, pad));
+
+	let mut shares: Vec<(u8, Vec<u8>)> = (1..=n as u16)
+		.map(|i| (i as u8, Vec::with_capacity(payload.len() / k)))
+		.collect();
+	for chunk in payload.chunks(k) {
+		let column = Matrix::new(chunk.iter().map(|&b| vec![GF256(b)]).collect()).unwrap();
+		let out = gen.clone() * column;
+		for (share, row) in shares.iter_mut().zip(out.contents.iter()) {
+			share.1.push(row[0].byte());
+		}
+	}
+	shares
+}
+
+/// Reconstructs the data originally passed to [`split`] from any `k` of its
+/// shares
+///
+/// Returns `None` if fewer than `k` shares are given, the shares carry
+/// different lengths, or the chosen shares' generator rows turn out to be
+/// singular (only possible with duplicate/invalid share ids).
+pub fn reconstruct(shares: &[(u8, Vec<u8>)], k: usize) -> Option<Vec<u8>> {
+	if shares.len() < k {
+		return None
+	}
+	let used = &shares[..k];
+	let ids: Vec<u8> = used.iter().map(|(id, _)| *id).collect();
+	let inv = invert(&share_matrix(&ids)?)?;
+
+	let share_len = used[0].1.len();
+	if used.iter().any(|(_, bytes)| bytes.len() != share_len) {
+		return None
+	}
+
+	let mut payload = Vec::with_capacity(share_len * k);
+	for col in 0..share_len {
+		let column = Matrix::new(used.iter().map(|(_, bytes)| vec![GF256(bytes[col])]).collect())?;
+		let original = inv.clone() * column;
+		for row in original.contents.iter() {
+			payload.push(row[0].byte());
+		}
+	}
+
+	if payload.len() < 8 {
+		return None
+	}
+	let len = u64::from_le_bytes(payload[..8].try_into().unwrap()) as usize;
+	let rest = &payload[8..];
+	if len > rest.len() {
+		return None
+	}
+	Some(rest[..len].to_vec())
+}
@@ -0,0 +1,9 @@
+//! `lin_alg` - a small generic linear algebra library
+
+pub mod core;
+pub mod decomposition;
+pub mod field;
+pub mod ida;
+
+pub use crate::core::{Matrix, ERO};
+pub use crate::field::{Field, GF256};